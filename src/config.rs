@@ -1,9 +1,22 @@
+use std::cmp::Reverse;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{LazyLock, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use serde::{Deserialize, Serialize};
 
+use crate::keymap::Keymap;
+use crate::storage::{STORE, EntryStore, import_legacy_toml};
+use crate::theme::Theme;
+
+const DEFAULT_MAX_ENTRIES: usize = 200;
+
+/// Disambiguates PNG filenames for captures taken within the same second,
+/// since `now_unix()` alone isn't unique enough for back-to-back screenshots.
+static IMAGE_SEQ: AtomicU64 = AtomicU64::new(0);
+
 pub static CONFIG: LazyLock<Mutex<Config>> =
     LazyLock::new(|| Mutex::new(Config::open().expect("Failed to open config")));
 
@@ -20,15 +33,103 @@ pub static EXE_NAME: LazyLock<String> = LazyLock::new(|| {
         .expect("Failed to get EXE name")
 });
 
+/// Directory captured clipboard images are saved to as PNG files, referenced
+/// by an `Entry`'s `content` path.
+pub static IMAGES_DIR: LazyLock<PathBuf> = LazyLock::new(|| EXE_PATH.with_file_name("made_images"));
+
+/// What an `Entry`'s `content` holds: literal text, or a path to a PNG file
+/// captured from the clipboard.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum EntryKind {
+    Text,
+    Image { width: u32, height: u32 },
+}
+
+impl Default for EntryKind {
+    fn default() -> Self {
+        EntryKind::Text
+    }
+}
+
+/// A single captured clipboard snippet, with the metadata needed to pin it
+/// and to rank it by recency/frequency of use.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub content: String,
+    pub created_at: i64,
+    pub last_used: i64,
+    pub use_count: u64,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub kind: EntryKind,
+}
+
+impl Entry {
+    fn new(content: String) -> Self {
+        let now = now_unix();
+
+        Self {
+            content,
+            created_at: now,
+            last_used: now,
+            use_count: 1,
+            pinned: false,
+            kind: EntryKind::Text,
+        }
+    }
+
+    /// Builds an entry for one migrated from a pre-SQLite `made.toml`.
+    pub(crate) fn imported(content: String) -> Self {
+        Self::new(content)
+    }
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Sorts entries for display: pinned entries float to the top, then ties
+/// break by most-used, then most-recently-used.
+pub fn sort_entries(entries: &mut [Entry]) {
+    entries.sort_by_key(|entry| {
+        (
+            Reverse(entry.pinned),
+            Reverse(entry.use_count),
+            Reverse(entry.last_used),
+        )
+    });
+}
+
+/// Settings persisted in `made.toml`. Entries themselves live in `made.db`
+/// (see `storage`) and are loaded into `texts` at startup; `texts` is never
+/// written back into the TOML file.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
-    pub texts: Vec<String>,
+    #[serde(skip)]
+    pub texts: Vec<Entry>,
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+    #[serde(default)]
+    pub keymap: Keymap,
+    #[serde(default)]
+    pub theme: Theme,
+}
+
+fn default_max_entries() -> usize {
+    DEFAULT_MAX_ENTRIES
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
-            texts: Vec::with_capacity(200),
+            texts: Vec::new(),
+            max_entries: DEFAULT_MAX_ENTRIES,
+            keymap: Keymap::default(),
+            theme: Theme::default(),
         }
     }
 }
@@ -37,11 +138,24 @@ impl Config {
     pub fn open() -> Result<Self> {
         let default_config = Config::default();
 
-        Config::read().or_else(|_e| {
+        let mut config = Config::read().or_else(|_e| {
             let toml_str = toml::to_string_pretty(&default_config)?;
             std::fs::write(&*CONFIG_PATH, toml_str)?;
-            Ok(default_config)
-        })
+            Ok::<Config, anyhow::Error>(default_config)
+        })?;
+
+        let texts = STORE.load()?;
+        if texts.is_empty() {
+            let imported = import_legacy_toml();
+            for entry in &imported {
+                STORE.upsert(entry)?;
+            }
+        }
+
+        STORE.enforce_capacity(config.max_entries)?;
+        config.texts = STORE.load()?;
+
+        Ok(config)
     }
 
     fn read() -> Result<Self> {
@@ -50,6 +164,9 @@ impl Config {
         Ok(toml_config)
     }
 
+    /// Persists settings (`[keymap]`, `[theme]`, `max_entries`) to
+    /// `made.toml`. Entries are persisted incrementally to `made.db`
+    /// instead, see `push_text`/`toggle_pinned`/`delete_entry`.
     pub fn save(&self) {
         let toml_str = toml::to_string_pretty(self)
             .expect("Failed to serialize ConfigToml structure as a String of TOML.");
@@ -58,9 +175,83 @@ impl Config {
     }
 
     pub fn push_text(&mut self, text: String) {
-        if !self.texts.contains(&text) {
-            self.texts.push(text.trim().to_owned());
-            self.save();
+        let text = text.trim().to_owned();
+        if text.is_empty() {
+            return;
+        }
+
+        if let Some(entry) = self.texts.iter_mut().find(|entry| entry.content == text) {
+            entry.use_count += 1;
+            entry.last_used = now_unix();
+            let _ = STORE.upsert(entry);
+        } else {
+            let entry = Entry::new(text);
+            let _ = STORE.upsert(&entry);
+            self.texts.push(entry);
+        }
+
+        if STORE.enforce_capacity(self.max_entries).is_ok()
+            && let Ok(texts) = STORE.load()
+        {
+            self.texts = texts;
+        }
+    }
+
+    /// Saves a captured RGBA bitmap as a PNG under `IMAGES_DIR` and records
+    /// it as a new image entry.
+    pub fn push_image(&mut self, width: u32, height: u32, rgba: Vec<u8>) -> Result<()> {
+        let Some(image) = image::RgbaImage::from_raw(width, height, rgba) else {
+            bail!("captured image data does not match its reported dimensions");
+        };
+
+        std::fs::create_dir_all(&*IMAGES_DIR)?;
+        let seq = IMAGE_SEQ.fetch_add(1, Ordering::Relaxed);
+        let path = IMAGES_DIR.join(format!("{}-{seq}.png", now_unix()));
+        image.save(&path)?;
+
+        let mut entry = Entry::new(path.to_string_lossy().into_owned());
+        entry.kind = EntryKind::Image { width, height };
+
+        STORE.upsert(&entry)?;
+        self.texts.push(entry);
+
+        if STORE.enforce_capacity(self.max_entries).is_ok()
+            && let Ok(texts) = STORE.load()
+        {
+            self.texts = texts;
         }
+
+        Ok(())
+    }
+
+    /// Bumps `use_count`/`last_used` for the entry with the given content,
+    /// e.g. on copy-back. Unlike `push_text`, never creates a new entry.
+    pub fn touch_entry(&mut self, content: &str) {
+        if let Some(entry) = self.texts.iter_mut().find(|entry| entry.content == content) {
+            entry.use_count += 1;
+            entry.last_used = now_unix();
+            let _ = STORE.upsert(entry);
+        }
+    }
+
+    /// Toggles the pinned flag of the entry with the given content, if any.
+    pub fn toggle_pinned(&mut self, content: &str) {
+        if let Some(entry) = self.texts.iter_mut().find(|entry| entry.content == content) {
+            entry.pinned = !entry.pinned;
+            let _ = STORE.upsert(entry);
+        }
+    }
+
+    /// Removes the entry with the given content, if any, deleting the PNG
+    /// backing an image entry.
+    pub fn delete_entry(&mut self, content: &str) {
+        if let Some(entry) = self.texts.iter().find(|entry| entry.content == content)
+            && matches!(entry.kind, EntryKind::Image { .. })
+        {
+            let _ = std::fs::remove_file(content);
+        }
+
+        self.texts.retain(|entry| entry.content != content);
+        let _ = STORE.delete(content);
     }
 }
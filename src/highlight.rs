@@ -0,0 +1,85 @@
+use std::sync::LazyLock;
+
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+static THEME: LazyLock<Theme> = LazyLock::new(|| {
+    let mut theme_set = ThemeSet::load_defaults();
+    theme_set
+        .themes
+        .remove("base16-ocean.dark")
+        .expect("bundled syntect theme `base16-ocean.dark` is missing")
+});
+
+/// Guesses a highlighting syntax for `content` from a fenced-code-block
+/// language hint, a shebang line, or a light keyword heuristic, falling
+/// back to plain text.
+pub fn detect_syntax(content: &str) -> &'static SyntaxReference {
+    fenced_code_lang(content)
+        .and_then(|lang| SYNTAX_SET.find_syntax_by_token(lang))
+        .or_else(|| shebang_lang(content).and_then(|lang| SYNTAX_SET.find_syntax_by_token(lang)))
+        .or_else(|| guess_by_keyword(content))
+        .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text())
+}
+
+fn fenced_code_lang(content: &str) -> Option<&str> {
+    let first_line = content.lines().next()?.trim();
+    let lang = first_line.strip_prefix("```")?;
+    (!lang.is_empty()).then_some(lang)
+}
+
+fn shebang_lang(content: &str) -> Option<&str> {
+    let first_line = content.lines().next()?.trim();
+    let path = first_line.strip_prefix("#!")?.trim();
+    let token = path.rsplit('/').next()?;
+    let token = token.split_whitespace().last()?;
+    Some(token.trim_end_matches(char::is_numeric))
+}
+
+fn guess_by_keyword(content: &str) -> Option<&'static SyntaxReference> {
+    const HINTS: &[(&str, &str)] = &[
+        ("fn main(", "rs"),
+        ("def ", "py"),
+        ("#include", "c"),
+        ("function ", "js"),
+        ("<?php", "php"),
+        ("SELECT ", "sql"),
+    ];
+
+    HINTS
+        .iter()
+        .find(|(needle, _)| content.contains(needle))
+        .and_then(|(_, ext)| SYNTAX_SET.find_syntax_by_extension(ext))
+}
+
+/// Highlights `content` with `syntax` and converts it into ratatui `Line`s,
+/// one per source line, for rendering in the preview panel.
+pub fn highlight_lines(content: &str, syntax: &SyntaxReference) -> Vec<Line<'static>> {
+    let mut highlighter = HighlightLines::new(syntax, &THEME);
+
+    LinesWithEndings::from(content)
+        .map(|line| {
+            let ranges = highlighter
+                .highlight_line(line, &SYNTAX_SET)
+                .unwrap_or_default();
+
+            let spans = ranges
+                .into_iter()
+                .map(|(style, text)| Span::styled(text.to_owned(), syntect_to_ratatui(style)))
+                .collect::<Vec<_>>();
+
+            Line::from(spans)
+        })
+        .collect()
+}
+
+fn syntect_to_ratatui(style: syntect::highlighting::Style) -> Style {
+    let fg = style.foreground;
+    Style::new().fg(Color::Rgb(fg.r, fg.g, fg.b))
+}
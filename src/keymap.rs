@@ -0,0 +1,219 @@
+use anyhow::{Result, bail};
+use crossterm::event::{KeyCode, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use win_hotkeys::VKey;
+
+/// A named, rebindable action. Matches the keys accepted in the
+/// `[keymap]` table of `made.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Capture,
+    CopySelected,
+    Next,
+    Prev,
+    ClearSearch,
+    Quit,
+    TogglePin,
+    DeleteEntry,
+}
+
+/// A parsed "Modifier+Modifier+Key" spec, e.g. `"Ctrl+Shift+V"`.
+#[derive(Debug, Clone)]
+pub struct KeySpec {
+    pub modifiers: Vec<String>,
+    pub key: String,
+}
+
+impl KeySpec {
+    fn parse(spec: &str) -> Result<Self> {
+        let mut parts: Vec<&str> = spec.split('+').map(str::trim).collect();
+        let Some(key) = parts.pop() else {
+            bail!("empty key spec");
+        };
+        if key.is_empty() {
+            bail!("key spec `{spec}` is missing a key");
+        }
+
+        Ok(Self {
+            modifiers: parts.into_iter().map(str::to_owned).collect(),
+            key: key.to_owned(),
+        })
+    }
+
+    /// Resolves this spec to the `win_hotkeys` modifier + key list used by
+    /// the global capture hotkey thread.
+    pub fn to_vkeys(&self) -> Result<(Vec<VKey>, VKey)> {
+        let mut modifiers = Vec::with_capacity(self.modifiers.len());
+        for modifier in &self.modifiers {
+            modifiers.push(parse_vkey_modifier(modifier)?);
+        }
+
+        Ok((modifiers, parse_vkey(&self.key)?))
+    }
+
+    /// Resolves this spec to the `crossterm` key code + modifiers used by
+    /// the in-TUI dispatch.
+    pub fn to_crossterm(&self) -> Result<(KeyModifiers, KeyCode)> {
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in &self.modifiers {
+            modifiers |= parse_crossterm_modifier(modifier)?;
+        }
+
+        Ok((modifiers, parse_crossterm_key(&self.key)?))
+    }
+}
+
+fn parse_vkey_modifier(name: &str) -> Result<VKey> {
+    match name.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => Ok(VKey::Control),
+        "alt" | "menu" => Ok(VKey::Menu),
+        "shift" => Ok(VKey::Shift),
+        other => bail!("unknown modifier `{other}`"),
+    }
+}
+
+fn parse_vkey(name: &str) -> Result<VKey> {
+    if name.len() == 1 {
+        let ch = name.chars().next().unwrap().to_ascii_uppercase();
+        if ch.is_ascii_alphabetic() {
+            return VKey::from_keyname(&ch.to_string())
+                .map_err(|_| anyhow::anyhow!("unknown key `{name}`"));
+        }
+    }
+
+    VKey::from_keyname(name).map_err(|_| anyhow::anyhow!("unknown key `{name}`"))
+}
+
+fn parse_crossterm_modifier(name: &str) -> Result<KeyModifiers> {
+    match name.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => Ok(KeyModifiers::CONTROL),
+        "alt" | "menu" => Ok(KeyModifiers::ALT),
+        "shift" => Ok(KeyModifiers::SHIFT),
+        other => bail!("unknown modifier `{other}`"),
+    }
+}
+
+fn parse_crossterm_key(name: &str) -> Result<KeyCode> {
+    if name.chars().count() == 1 {
+        return Ok(KeyCode::Char(name.chars().next().unwrap().to_ascii_lowercase()));
+    }
+
+    match name.to_ascii_lowercase().as_str() {
+        "esc" | "escape" => Ok(KeyCode::Esc),
+        "enter" | "return" => Ok(KeyCode::Enter),
+        "tab" => Ok(KeyCode::Tab),
+        "backspace" => Ok(KeyCode::Backspace),
+        "delete" | "del" => Ok(KeyCode::Delete),
+        "up" => Ok(KeyCode::Up),
+        "down" => Ok(KeyCode::Down),
+        "left" => Ok(KeyCode::Left),
+        "right" => Ok(KeyCode::Right),
+        "home" => Ok(KeyCode::Home),
+        "end" => Ok(KeyCode::End),
+        other => bail!("unknown key `{other}`"),
+    }
+}
+
+/// User-configurable bindings for the global capture hotkey and every
+/// in-TUI action, loaded from the `[keymap]` table of `made.toml`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Keymap {
+    pub capture: String,
+    pub copy_selected: String,
+    pub next: String,
+    pub prev: String,
+    pub clear_search: String,
+    pub quit: String,
+    #[serde(default = "default_toggle_pin")]
+    pub toggle_pin: String,
+    #[serde(default = "default_delete_entry")]
+    pub delete_entry: String,
+}
+
+fn default_toggle_pin() -> String {
+    "Ctrl+P".to_owned()
+}
+
+fn default_delete_entry() -> String {
+    "Ctrl+D".to_owned()
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            capture: "Alt+C".to_owned(),
+            copy_selected: "Enter".to_owned(),
+            next: "Down".to_owned(),
+            prev: "Up".to_owned(),
+            clear_search: "Esc".to_owned(),
+            quit: "Esc".to_owned(),
+            toggle_pin: default_toggle_pin(),
+            delete_entry: default_delete_entry(),
+        }
+    }
+}
+
+impl Keymap {
+    pub fn spec(&self, action: Action) -> Result<KeySpec> {
+        let raw = match action {
+            Action::Capture => &self.capture,
+            Action::CopySelected => &self.copy_selected,
+            Action::Next => &self.next,
+            Action::Prev => &self.prev,
+            Action::ClearSearch => &self.clear_search,
+            Action::Quit => &self.quit,
+            Action::TogglePin => &self.toggle_pin,
+            Action::DeleteEntry => &self.delete_entry,
+        };
+
+        KeySpec::parse(raw)
+    }
+
+    /// Resolves which action (if any) the given crossterm key event maps to.
+    /// `Action::Capture` is deliberately excluded: it's a global hotkey
+    /// resolved via `KeySpec::to_vkeys` on the `win_hotkeys` thread, not
+    /// something the TUI's crossterm event loop ever dispatches.
+    pub fn action_for(&self, modifiers: KeyModifiers, code: KeyCode) -> Option<Action> {
+        let (modifiers, code) = normalize_letter_binding(modifiers, code);
+
+        for action in [
+            Action::CopySelected,
+            Action::Next,
+            Action::Prev,
+            Action::ClearSearch,
+            Action::Quit,
+            Action::TogglePin,
+            Action::DeleteEntry,
+        ] {
+            let Ok(spec) = self.spec(action) else {
+                continue;
+            };
+            let Ok((want_modifiers, want_code)) = spec.to_crossterm() else {
+                continue;
+            };
+            let (want_modifiers, want_code) = normalize_letter_binding(want_modifiers, want_code);
+
+            if want_modifiers == modifiers && want_code == code {
+                return Some(action);
+            }
+        }
+
+        None
+    }
+}
+
+/// Normalizes a modifiers+key-code pair for letter keys by lowercasing the
+/// character and dropping the `SHIFT` bit. Crossterm reports Shift+letter as
+/// an upper-cased `Char` with `SHIFT` set rather than a distinct key code, so
+/// a strict comparison against `parse_crossterm_key`'s always-lowercased spec
+/// would make any Shift-inclusive letter binding (e.g. `"Ctrl+Shift+V"`)
+/// permanently unreachable.
+fn normalize_letter_binding(modifiers: KeyModifiers, code: KeyCode) -> (KeyModifiers, KeyCode) {
+    match code {
+        KeyCode::Char(c) => (
+            modifiers - KeyModifiers::SHIFT,
+            KeyCode::Char(c.to_ascii_lowercase()),
+        ),
+        _ => (modifiers, code),
+    }
+}
@@ -1,30 +1,44 @@
 mod config;
+mod highlight;
+mod keymap;
 mod pinyin;
+mod preview;
 mod single_instance;
+mod storage;
+mod theme;
 mod util;
 
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::path::PathBuf;
 use std::sync::{
     Arc, LazyLock, Mutex,
     atomic::{AtomicBool, Ordering},
 };
 
 use anyhow::{Context, Result};
-use arboard::Clipboard;
+use arboard::{Clipboard, ImageData};
 use crossterm::event::{Event, KeyCode};
 use ratatui::{
     DefaultTerminal,
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style, Stylize},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
     widgets::{
         Block, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
-        ScrollbarState, StatefulWidget, Widget,
+        ScrollbarState, StatefulWidget, Widget, Wrap,
     },
 };
 use win_hotkeys::HotkeyManager;
-use win_hotkeys::VKey;
 
-use crate::{config::CONFIG, pinyin::match_pinyin, single_instance::SingleInstance};
+use crate::{
+    config::{CONFIG, Entry, EntryKind, sort_entries},
+    keymap::Action,
+    pinyin::{PinyinMatch, match_pinyin},
+    single_instance::SingleInstance,
+    theme::ResolvedTheme,
+};
 
 pub static CLIPBOARD: LazyLock<Mutex<Clipboard>> =
     LazyLock::new(|| Mutex::new(Clipboard::new().expect("Failed to create new clipboard")));
@@ -35,12 +49,33 @@ pub static UPDATE_TUI_TEXT: LazyLock<Arc<AtomicBool>> =
 fn main() -> Result<()> {
     let _single_instance = SingleInstance::new()?;
 
+    let (capture_modifiers, capture_key) = CONFIG
+        .lock()
+        .unwrap()
+        .keymap
+        .spec(Action::Capture)
+        .and_then(|spec| spec.to_vkeys())
+        .context("Failed to parse [keymap] capture binding")?;
+
     std::thread::spawn(move || {
         let mut hkm = HotkeyManager::new();
 
-        hkm.register_hotkey(VKey::C, &[VKey::Menu], move || {
-            let text = CLIPBOARD.lock().unwrap().get_text().unwrap();
-            CONFIG.lock().unwrap().push_text(text);
+        hkm.register_hotkey(capture_key, &capture_modifiers, move || {
+            let mut clipboard = CLIPBOARD.lock().unwrap();
+
+            if let Ok(image) = clipboard.get_image() {
+                let (width, height) = (image.width as u32, image.height as u32);
+                let rgba = image.bytes.into_owned();
+                drop(clipboard);
+
+                if let Err(err) = CONFIG.lock().unwrap().push_image(width, height, rgba) {
+                    eprintln!("Failed to save captured image: {err:#}");
+                }
+            } else if let Ok(text) = clipboard.get_text() {
+                drop(clipboard);
+                CONFIG.lock().unwrap().push_text(text);
+            }
+
             UPDATE_TUI_TEXT.store(true, Ordering::Relaxed);
         })
         .unwrap();
@@ -54,10 +89,60 @@ fn main() -> Result<()> {
 }
 
 struct TextList {
-    items: Vec<String>,
+    items: Vec<Entry>,
     state: ListState,
 }
 
+/// Splits `text` into spans so the matched substring renders with
+/// `highlight_style` and the rest stays unstyled.
+fn highlighted_spans(text: &str, m: PinyinMatch, highlight_style: Style) -> Vec<Span<'static>> {
+    let start = m.start.min(text.len());
+    let end = (m.start + m.len).min(text.len());
+
+    let mut spans = Vec::with_capacity(3);
+    if start > 0 {
+        spans.push(Span::raw(text[..start].to_owned()));
+    }
+    if end > start {
+        spans.push(Span::styled(text[start..end].to_owned(), highlight_style));
+    }
+    if end < text.len() {
+        spans.push(Span::raw(text[end..].to_owned()));
+    }
+
+    spans
+}
+
+fn loaded_entries() -> Vec<Entry> {
+    let mut entries = CONFIG.lock().unwrap().texts.clone();
+    sort_entries(&mut entries);
+    entries
+}
+
+/// Writes an entry back to the system clipboard, decoding the PNG on disk
+/// for image entries.
+fn copy_entry_to_clipboard(entry: &Entry) -> Result<()> {
+    match entry.kind {
+        EntryKind::Text => {
+            CLIPBOARD.lock().unwrap().set_text(entry.content.clone())?;
+        }
+        EntryKind::Image { width, height } => {
+            let rgba = image::open(&entry.content)
+                .context("Failed to decode cached clipboard image")?
+                .to_rgba8()
+                .into_raw();
+
+            CLIPBOARD.lock().unwrap().set_image(ImageData {
+                width: width as usize,
+                height: height as usize,
+                bytes: Cow::Owned(rgba),
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
 pub struct Tui {
     exit: bool,
     //
@@ -66,13 +151,28 @@ pub struct Tui {
     //
     text_list: TextList,
     filtered_indices: Vec<usize>,
+    match_ranges: Vec<PinyinMatch>,
     //
     scrollbar_state: ScrollbarState,
+    //
+    theme: ResolvedTheme,
+    preview_area: Rect,
+    /// Path of the image entry currently transmitted via Kitty graphics, if
+    /// any, so `render_image_overlay` can skip redundant retransmission and
+    /// clear the bitmap once the selection moves away from it.
+    last_rendered_image: Option<PathBuf>,
 }
 
 impl Default for Tui {
     fn default() -> Self {
-        let items = CONFIG.lock().unwrap().texts.clone();
+        let config = CONFIG.lock().unwrap();
+        let theme = config.theme.resolve().unwrap_or_else(|err| {
+            eprintln!("Warning: failed to resolve [theme] colors, using defaults: {err:#}");
+            ResolvedTheme::default()
+        });
+        drop(config);
+
+        let items = loaded_entries();
 
         let mut tui = Self {
             exit: false,
@@ -83,7 +183,11 @@ impl Default for Tui {
                 state: ListState::default().with_selected(Some(0)),
             },
             filtered_indices: Vec::new(),
+            match_ranges: Vec::new(),
             scrollbar_state: ScrollbarState::new(0),
+            theme,
+            preview_area: Rect::default(),
+            last_rendered_image: None,
         };
 
         tui.rebuild_filter();
@@ -95,6 +199,7 @@ impl Tui {
     fn run(mut self, terminal: &mut DefaultTerminal) -> Result<()> {
         while !self.exit {
             terminal.draw(|frame| frame.render_widget(&mut self, frame.area()))?;
+            self.render_image_overlay()?;
             self.handle_events()?;
             self.update_text_list();
         }
@@ -102,16 +207,59 @@ impl Tui {
         Ok(())
     }
 
+    /// Selected entry, resolved through `filtered_indices`.
+    fn selected_entry(&self) -> Option<&Entry> {
+        let selected = self.text_list.state.selected()?;
+        let &real_index = self.filtered_indices.get(selected)?;
+        self.text_list.items.get(real_index)
+    }
+
+    /// Draws the selected image entry directly to stdout via the Kitty
+    /// graphics protocol, bypassing ratatui's cell buffer. A no-op for text
+    /// entries or terminals without Kitty support (see `render_preview`).
+    ///
+    /// Tracks the last-transmitted image so an unchanged selection doesn't
+    /// resend the same base64 payload every tick, and so moving off an image
+    /// entry clears the bitmap instead of leaving it painted over the
+    /// terminal (ratatui never redraws cells it didn't itself write to).
+    fn render_image_overlay(&mut self) -> Result<()> {
+        let selected_image = self.selected_entry().and_then(|entry| match entry.kind {
+            EntryKind::Image { .. } if preview::supports_kitty_graphics() => {
+                Some(PathBuf::from(&entry.content))
+            }
+            _ => None,
+        });
+
+        if selected_image != self.last_rendered_image {
+            if self.last_rendered_image.is_some() {
+                preview::clear_kitty_image()?;
+            }
+            if let Some(path) = &selected_image {
+                preview::render_kitty_image(path, self.preview_area)
+                    .context("Failed to render image preview")?;
+            }
+            self.last_rendered_image = selected_image;
+        }
+
+        Ok(())
+    }
+
     fn update_text_list(&mut self) {
         while UPDATE_TUI_TEXT.swap(false, Ordering::Relaxed) {
-            self.text_list = TextList {
-                items: CONFIG.lock().unwrap().texts.clone(),
-                state: ListState::default().with_selected(Some(0)),
-            };
-            self.rebuild_filter();
+            self.reload_text_list();
         }
     }
 
+    /// Re-reads entries from `CONFIG` and rebuilds the filter, used after a
+    /// capture and after any action that mutates an entry in place.
+    fn reload_text_list(&mut self) {
+        self.text_list = TextList {
+            items: loaded_entries(),
+            state: ListState::default().with_selected(Some(0)),
+        };
+        self.rebuild_filter();
+    }
+
     fn handle_events(&mut self) -> Result<()> {
         let _ = crossterm::event::poll(std::time::Duration::from_millis(250))
             .context("event poll failed")?;
@@ -121,8 +269,14 @@ impl Tui {
                 return Ok(());
             }
 
-            match key.code {
-                KeyCode::Esc => {
+            let action = CONFIG
+                .lock()
+                .unwrap()
+                .keymap
+                .action_for(key.modifiers, key.code);
+
+            match action {
+                Some(Action::ClearSearch) => {
                     if self.search_text.trim().is_empty() {
                         self.exit = true;
                     } else {
@@ -130,35 +284,67 @@ impl Tui {
                         self.rebuild_filter();
                     }
                 }
-                KeyCode::Down => self.select_next(),
-                KeyCode::Up => self.select_previous(),
-                KeyCode::Left => {
-                    let cursor_moved_left = self.character_index.saturating_sub(1);
-                    self.character_index = self.clamp_cursor(cursor_moved_left);
-                }
-                KeyCode::Right => {
-                    let cursor_moved_right = self.character_index.saturating_add(1);
-                    self.character_index = self.clamp_cursor(cursor_moved_right);
-                }
-                KeyCode::Home => self.select_first(),
-                KeyCode::End => self.select_last(),
-                KeyCode::Backspace => {
-                    self.delete_char();
-                    self.rebuild_filter();
+                Some(Action::Quit) => self.exit = true,
+                Some(Action::Next) => self.select_next(),
+                Some(Action::Prev) => self.select_previous(),
+                Some(Action::CopySelected) => {
+                    if let Some(&real_index) = self
+                        .text_list
+                        .state
+                        .selected()
+                        .and_then(|selected| self.filtered_indices.get(selected))
+                    {
+                        let entry = self.text_list.items[real_index].clone();
+                        copy_entry_to_clipboard(&entry)?;
+                        CONFIG.lock().unwrap().touch_entry(&entry.content);
+                        self.reload_text_list();
+                    }
                 }
-                KeyCode::Char(to_insert) => {
-                    self.enter_char(to_insert);
-                    self.rebuild_filter();
+                Some(Action::TogglePin) => {
+                    if let Some(&real_index) = self
+                        .text_list
+                        .state
+                        .selected()
+                        .and_then(|selected| self.filtered_indices.get(selected))
+                    {
+                        let content = self.text_list.items[real_index].content.clone();
+                        CONFIG.lock().unwrap().toggle_pinned(&content);
+                        self.reload_text_list();
+                    }
                 }
-                KeyCode::Enter => {
-                    if let Some(selected) = self.text_list.state.selected()
-                        && let Some(&real_index) = self.filtered_indices.get(selected)
+                Some(Action::DeleteEntry) => {
+                    if let Some(&real_index) = self
+                        .text_list
+                        .state
+                        .selected()
+                        .and_then(|selected| self.filtered_indices.get(selected))
                     {
-                        let text = self.text_list.items[real_index].clone();
-                        CLIPBOARD.lock().unwrap().set_text(text)?;
+                        let content = self.text_list.items[real_index].content.clone();
+                        CONFIG.lock().unwrap().delete_entry(&content);
+                        self.reload_text_list();
                     }
                 }
-                _ => {}
+                _ => match key.code {
+                    KeyCode::Left => {
+                        let cursor_moved_left = self.character_index.saturating_sub(1);
+                        self.character_index = self.clamp_cursor(cursor_moved_left);
+                    }
+                    KeyCode::Right => {
+                        let cursor_moved_right = self.character_index.saturating_add(1);
+                        self.character_index = self.clamp_cursor(cursor_moved_right);
+                    }
+                    KeyCode::Home => self.select_first(),
+                    KeyCode::End => self.select_last(),
+                    KeyCode::Backspace => {
+                        self.delete_char();
+                        self.rebuild_filter();
+                    }
+                    KeyCode::Char(to_insert) => {
+                        self.enter_char(to_insert);
+                        self.rebuild_filter();
+                    }
+                    _ => {}
+                },
             }
         }
 
@@ -239,21 +425,27 @@ impl Tui {
 
         if search.is_empty() {
             self.filtered_indices.clear();
+            self.match_ranges.clear();
             self.text_list.state.select(None);
             return;
         }
 
-        self.filtered_indices = self
+        let mut matches: Vec<(usize, PinyinMatch)> = self
             .text_list
             .items
             .iter()
             .enumerate()
-            .filter_map(|(i, text)| {
-                let matched = match_pinyin(search, text);
-                matched.then_some(i)
+            .filter_map(|(i, entry)| {
+                let m = match_pinyin(search, &entry.content)?;
+                Some((i, m))
             })
             .collect();
 
+        matches.sort_by_key(|(_, m)| Reverse(m.score()));
+
+        self.filtered_indices = matches.iter().map(|&(i, _)| i).collect();
+        self.match_ranges = matches.into_iter().map(|(_, m)| m).collect();
+
         self.scrollbar_state = ScrollbarState::new(self.filtered_indices.len());
         // 修正选中状态
         if self.filtered_indices.is_empty() {
@@ -273,23 +465,30 @@ impl Widget for &mut Tui {
         ]);
         let [header_area, content_area, search_area] = area.layout(&main_layout);
 
-        Tui::render_header(header_area, buf);
-        self.render_list(content_area, buf);
-        self.render_scrollbar(content_area, buf);
+        let content_layout = Layout::horizontal([Constraint::Fill(3), Constraint::Fill(2)]);
+        let [list_area, preview_area] = content_area.layout(&content_layout);
+
+        self.render_header(header_area, buf);
+        self.render_list(list_area, buf);
+        self.render_scrollbar(list_area, buf);
+        self.render_preview(preview_area, buf);
         self.render_search(search_area, buf);
     }
 }
 
 impl Tui {
-    fn render_header(area: Rect, buf: &mut Buffer) {
+    fn render_header(&self, area: Rect, buf: &mut Buffer) {
         Paragraph::new("made(玛德)")
             .bold()
+            .fg(self.theme.header)
             .centered()
             .render(area, buf);
     }
 
     fn render_search(&self, area: Rect, buf: &mut Buffer) {
-        let block = Block::bordered().title(" 搜索");
+        let block = Block::bordered()
+            .title(" 搜索")
+            .border_style(Style::new().fg(self.theme.border));
 
         Paragraph::new(self.search_text.clone())
             .block(block)
@@ -319,27 +518,88 @@ impl Tui {
             .iter()
             .enumerate()
             .map(|(display_index, &real_index)| {
-                let text = &self.text_list.items[real_index];
+                let entry = &self.text_list.items[real_index];
 
                 let background = if display_index % 2 == 0 {
-                    Color::Rgb(25, 25, 25)
+                    self.theme.row_even
                 } else {
-                    Color::Rgb(42, 42, 42)
+                    self.theme.row_odd
                 };
 
-                ListItem::new(text.clone()).bg(background)
+                let mut spans = Vec::new();
+                if entry.pinned {
+                    spans.push(Span::raw("📌 "));
+                }
+
+                match entry.kind {
+                    EntryKind::Text => {
+                        let highlight_style = Style::new().fg(self.theme.match_highlight);
+                        match self.match_ranges.get(display_index) {
+                            Some(&m) => spans.extend(highlighted_spans(
+                                &entry.content,
+                                m,
+                                highlight_style,
+                            )),
+                            None => spans.push(Span::raw(entry.content.clone())),
+                        }
+                    }
+                    EntryKind::Image { width, height } => {
+                        spans.push(Span::raw(format!("🖼 image {width}x{height}")));
+                    }
+                }
+
+                ListItem::new(Line::from(spans)).bg(background)
             })
             .collect();
 
         let list = List::new(items)
-            .block(Block::bordered().title(" 结果"))
+            .block(
+                Block::bordered()
+                    .title(" 结果")
+                    .border_style(Style::new().fg(self.theme.border)),
+            )
             .highlight_style(
                 Style::new()
-                    .bg(Color::Rgb(66, 66, 66))
+                    .bg(self.theme.selected_bg)
+                    .fg(self.theme.selected_fg)
                     .add_modifier(Modifier::BOLD),
             )
             .highlight_symbol(">");
 
         StatefulWidget::render(list, area, buf, &mut self.text_list.state);
     }
+
+    /// Renders the full selected entry: its text, or for images a
+    /// dimension/size placeholder (the actual bitmap, when the terminal
+    /// supports it, is drawn separately by `render_image_overlay`).
+    fn render_preview(&mut self, area: Rect, buf: &mut Buffer) {
+        self.preview_area = area;
+
+        let block = Block::bordered()
+            .title(" 预览")
+            .border_style(Style::new().fg(self.theme.border));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+
+        match entry.kind {
+            EntryKind::Text => {
+                let syntax = highlight::detect_syntax(&entry.content);
+                let lines = highlight::highlight_lines(&entry.content, syntax);
+
+                Paragraph::new(lines)
+                    .wrap(Wrap { trim: false })
+                    .render(inner, buf);
+            }
+            EntryKind::Image { width, height } if !preview::supports_kitty_graphics() => {
+                Paragraph::new(format!("[image {width}x{height}]"))
+                    .centered()
+                    .render(inner, buf);
+            }
+            EntryKind::Image { .. } => {}
+        }
+    }
 }
@@ -1,9 +1,30 @@
 use ib_pinyin::{matcher::PinyinMatcher, pinyin::PinyinNotation};
 
-pub fn match_pinyin(input: &str, text: &str) -> bool {
+/// A matched byte range within the haystack, used to rank results and to
+/// highlight the matched substring in the result list.
+#[derive(Debug, Clone, Copy)]
+pub struct PinyinMatch {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl PinyinMatch {
+    /// Favors matches that start earlier and span more of the haystack
+    /// contiguously, so a prefix match outranks one found deep inside.
+    pub fn score(&self) -> i64 {
+        self.len as i64 - self.start as i64
+    }
+}
+
+pub fn match_pinyin(input: &str, text: &str) -> Option<PinyinMatch> {
     let matcher = PinyinMatcher::builder(input)
         .pinyin_notations(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter)
         .build();
 
-    matcher.is_match(text)
+    let found = matcher.find(text)?;
+
+    Some(PinyinMatch {
+        start: found.start(),
+        len: found.len(),
+    })
 }
@@ -0,0 +1,56 @@
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use crossterm::{cursor::MoveTo, queue};
+use ratatui::layout::Rect;
+
+/// Whether the current terminal advertises Kitty graphics protocol support.
+pub fn supports_kitty_graphics() -> bool {
+    std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || std::env::var("TERM")
+            .map(|term| term.contains("kitty"))
+            .unwrap_or(false)
+}
+
+/// Emits the Kitty graphics protocol escape sequence to display the PNG at
+/// `path` inside `area`, writing directly to stdout since ratatui's cell
+/// buffer has no concept of raw terminal graphics.
+pub fn render_kitty_image(path: &Path, area: Rect) -> Result<()> {
+    let bytes = std::fs::read(path).context("Failed to read cached preview image")?;
+    let encoded = STANDARD.encode(bytes);
+
+    let mut stdout = std::io::stdout();
+    queue!(stdout, MoveTo(area.x, area.y)).context("Failed to position cursor for preview")?;
+
+    let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(4096).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = if i + 1 < chunks.len() { 1 } else { 0 };
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+
+        if i == 0 {
+            write!(
+                stdout,
+                "\x1b_Ga=T,f=100,c={},r={},m={};{}\x1b\\",
+                area.width, area.height, more, chunk
+            )
+        } else {
+            write!(stdout, "\x1b_Gm={};{}\x1b\\", more, chunk)
+        }
+        .context("Failed to write Kitty graphics escape sequence")?;
+    }
+
+    stdout.flush().context("Failed to flush preview image")
+}
+
+/// Emits the Kitty graphics protocol delete action, clearing any
+/// previously-transmitted image placement. Called when the selection moves
+/// away from an image entry, since ratatui never redraws over the cells the
+/// bitmap occupies on its own.
+pub fn clear_kitty_image() -> Result<()> {
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b_Ga=d\x1b\\").context("Failed to clear previous Kitty image")?;
+    stdout.flush().context("Failed to flush Kitty image clear")
+}
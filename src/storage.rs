@@ -0,0 +1,189 @@
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+
+use anyhow::{Context, Result};
+use rusqlite::{Connection, params};
+
+use crate::config::{CONFIG_PATH, Entry, EntryKind};
+
+pub static STORE: LazyLock<SqliteStore> =
+    LazyLock::new(|| SqliteStore::open().expect("Failed to open made.db"));
+
+pub static DB_PATH: LazyLock<PathBuf> = LazyLock::new(|| CONFIG_PATH.with_file_name("made.db"));
+
+/// A persistence backend for clipboard entries. `SqliteStore` is the only
+/// implementation; the trait exists so `Config` depends on behavior
+/// (incremental upsert/delete with bounded capacity) rather than on
+/// `rusqlite` directly.
+pub trait EntryStore: Send + Sync {
+    fn load(&self) -> Result<Vec<Entry>>;
+    fn upsert(&self, entry: &Entry) -> Result<()>;
+    fn delete(&self, content: &str) -> Result<()>;
+    /// Evicts the oldest unpinned rows beyond `max_entries`, deleting the
+    /// PNG backing any evicted image entry so `made_images/` doesn't
+    /// accumulate orphaned files.
+    fn enforce_capacity(&self, max_entries: usize) -> Result<()>;
+}
+
+pub struct SqliteStore {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteStore {
+    pub fn open() -> Result<Self> {
+        let conn = Connection::open(&*DB_PATH).context("Failed to open made.db")?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS entries (
+                content    TEXT PRIMARY KEY,
+                created_at INTEGER NOT NULL,
+                last_used  INTEGER NOT NULL,
+                use_count  INTEGER NOT NULL,
+                pinned     INTEGER NOT NULL,
+                kind       TEXT NOT NULL DEFAULT 'text',
+                width      INTEGER,
+                height     INTEGER
+            )",
+            [],
+        )
+        .context("Failed to create entries table")?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+}
+
+impl EntryStore for SqliteStore {
+    fn load(&self) -> Result<Vec<Entry>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT content, created_at, last_used, use_count, pinned, kind, width, height
+             FROM entries",
+        )?;
+
+        let entries = stmt
+            .query_map([], |row| {
+                let kind = match row.get::<_, String>(5)?.as_str() {
+                    "image" => EntryKind::Image {
+                        width: row.get::<_, Option<i64>>(6)?.unwrap_or(0) as u32,
+                        height: row.get::<_, Option<i64>>(7)?.unwrap_or(0) as u32,
+                    },
+                    _ => EntryKind::Text,
+                };
+
+                Ok(Entry {
+                    content: row.get(0)?,
+                    created_at: row.get(1)?,
+                    last_used: row.get(2)?,
+                    use_count: row.get(3)?,
+                    pinned: row.get::<_, i64>(4)? != 0,
+                    kind,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to load entries from made.db")?;
+
+        Ok(entries)
+    }
+
+    fn upsert(&self, entry: &Entry) -> Result<()> {
+        let (kind, width, height) = match entry.kind {
+            EntryKind::Text => ("text", None::<i64>, None::<i64>),
+            EntryKind::Image { width, height } => ("image", Some(width as i64), Some(height as i64)),
+        };
+
+        self.conn
+            .lock()
+            .unwrap()
+            .execute(
+                "INSERT INTO entries (content, created_at, last_used, use_count, pinned, kind, width, height)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                 ON CONFLICT(content) DO UPDATE SET
+                    last_used = excluded.last_used,
+                    use_count = excluded.use_count,
+                    pinned = excluded.pinned,
+                    kind = excluded.kind,
+                    width = excluded.width,
+                    height = excluded.height",
+                params![
+                    entry.content,
+                    entry.created_at,
+                    entry.last_used,
+                    entry.use_count as i64,
+                    entry.pinned as i64,
+                    kind,
+                    width,
+                    height,
+                ],
+            )
+            .context("Failed to upsert entry into made.db")?;
+
+        Ok(())
+    }
+
+    fn delete(&self, content: &str) -> Result<()> {
+        self.conn
+            .lock()
+            .unwrap()
+            .execute("DELETE FROM entries WHERE content = ?1", params![content])
+            .context("Failed to delete entry from made.db")?;
+
+        Ok(())
+    }
+
+    fn enforce_capacity(&self, max_entries: usize) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let evicted_images = {
+            let mut stmt = conn.prepare(
+                "SELECT content FROM entries
+                 WHERE pinned = 0 AND kind = 'image' AND content NOT IN (
+                    SELECT content FROM entries WHERE pinned = 0
+                    ORDER BY last_used DESC LIMIT ?1
+                 )",
+            )?;
+            stmt.query_map(params![max_entries as i64], |row| row.get::<_, String>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()
+                .context("Failed to list entries pending eviction")?
+        };
+
+        conn.execute(
+            "DELETE FROM entries WHERE pinned = 0 AND content NOT IN (
+                SELECT content FROM entries WHERE pinned = 0
+                ORDER BY last_used DESC LIMIT ?1
+            )",
+            params![max_entries as i64],
+        )
+        .context("Failed to enforce made.db capacity")?;
+
+        for path in evicted_images {
+            let _ = std::fs::remove_file(path);
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads entries from a pre-SQLite `made.toml`, where `texts` was still a
+/// flat array, so existing configs migrate into the SQLite store on first
+/// run. Returns an empty list if the file is absent or in the new format.
+pub fn import_legacy_toml() -> Vec<Entry> {
+    let Ok(content) = std::fs::read_to_string(&*CONFIG_PATH) else {
+        return Vec::new();
+    };
+    let Ok(value) = content.parse::<toml::Value>() else {
+        return Vec::new();
+    };
+    let Some(texts) = value.get("texts").and_then(|texts| texts.as_array()) else {
+        return Vec::new();
+    };
+
+    texts
+        .iter()
+        .filter_map(|item| match item.as_str() {
+            Some(content) => Some(Entry::imported(content.to_owned())),
+            None => item.clone().try_into::<Entry>().ok(),
+        })
+        .collect()
+}
@@ -0,0 +1,112 @@
+use anyhow::Result;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+/// Colors for the list rows, selection, header, borders, and search-match
+/// highlight, loaded from the `[theme]`
+/// table of `made.toml`. Each field accepts a named color (`"black"`), a
+/// `#rrggbb` hex string, or an `rgb(r, g, b)` expression.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Theme {
+    pub row_even: String,
+    pub row_odd: String,
+    pub selected_bg: String,
+    pub selected_fg: String,
+    pub header: String,
+    pub border: String,
+    pub match_highlight: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            row_even: "rgb(25, 25, 25)".to_owned(),
+            row_odd: "rgb(42, 42, 42)".to_owned(),
+            selected_bg: "rgb(66, 66, 66)".to_owned(),
+            selected_fg: "white".to_owned(),
+            header: "white".to_owned(),
+            border: "white".to_owned(),
+            match_highlight: "yellow".to_owned(),
+        }
+    }
+}
+
+impl Theme {
+    pub fn resolve(&self) -> Result<ResolvedTheme> {
+        Ok(ResolvedTheme {
+            row_even: parse_color(&self.row_even)?,
+            row_odd: parse_color(&self.row_odd)?,
+            selected_bg: parse_color(&self.selected_bg)?,
+            selected_fg: parse_color(&self.selected_fg)?,
+            header: parse_color(&self.header)?,
+            border: parse_color(&self.border)?,
+            match_highlight: parse_color(&self.match_highlight)?,
+        })
+    }
+}
+
+/// The `[theme]` table converted into `ratatui::style::Color`s, stored on
+/// `Tui` and consulted during render.
+#[derive(Debug, Clone, Copy)]
+pub struct ResolvedTheme {
+    pub row_even: Color,
+    pub row_odd: Color,
+    pub selected_bg: Color,
+    pub selected_fg: Color,
+    pub header: Color,
+    pub border: Color,
+    pub match_highlight: Color,
+}
+
+impl Default for ResolvedTheme {
+    fn default() -> Self {
+        Theme::default()
+            .resolve()
+            .expect("default theme colors must parse")
+    }
+}
+
+fn parse_color(raw: &str) -> Result<Color> {
+    let trimmed = raw.trim();
+
+    if let Some(hex) = trimmed.strip_prefix('#') {
+        return parse_hex(hex).ok_or_else(|| anyhow::anyhow!("invalid hex color `{raw}`"));
+    }
+
+    if let Some(inner) = trimmed
+        .strip_prefix("rgb(")
+        .and_then(|s| s.strip_suffix(')'))
+    {
+        return parse_rgb_tuple(inner).ok_or_else(|| anyhow::anyhow!("invalid rgb color `{raw}`"));
+    }
+
+    trimmed
+        .parse::<Color>()
+        .map_err(|_| anyhow::anyhow!("unknown color `{raw}`"))
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if hex.len() != 6 {
+        return None;
+    }
+
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+
+    Some(Color::Rgb(r, g, b))
+}
+
+fn parse_rgb_tuple(inner: &str) -> Option<Color> {
+    let mut parts = inner.split(',').map(|p| p.trim().parse::<u8>());
+
+    let r = parts.next()?.ok()?;
+    let g = parts.next()?.ok()?;
+    let b = parts.next()?.ok()?;
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(Color::Rgb(r, g, b))
+}